@@ -0,0 +1,91 @@
+//! BLS12-381 aggregate-signature verification (min-pubkey-size variant).
+//!
+//! Lets a run be co-signed by several devices (e.g. a phone and a watch, or a
+//! relay collecting several witnesses) and compresses their N signatures
+//! into a single aggregate the guest verifies once, parallel to the
+//! secp256k1 ECDSA path in `main.rs`.
+//!
+//! This uses the proof-of-possession (`_POP_`) ciphersuite, which is only
+//! rogue-key-safe if every pubkey accepted into `bls_pubkeys` already had its
+//! proof of possession checked out-of-band at device-enrollment time (the
+//! guest has no per-run PoP to check here, and a run's GPS samples are not a
+//! place to carry one). Callers MUST reject any pubkey that hasn't passed PoP
+//! enrollment before it ever reaches `RunInput::bls_pubkeys`.
+//!
+//! Pairing math runs through `bls12_381` (zkcrypto), a pure-Rust crate with no
+//! C/asm dependency, rather than `blst`: the guest is a `no_std` riscv32im
+//! target and `blst`'s hand-written assembly backends have no guarantee of
+//! supporting it, while `bls12_381` is plain Rust and builds wherever `core`
+//! and `alloc` do.
+//!
+//! "Co-signed" means more than one signer: `verify_aggregate` rejects an
+//! aggregate with fewer than `MIN_COSIGNERS` keys so a single device can't
+//! pass itself off as a fleet attestation.
+
+use alloc::vec::Vec;
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective};
+use sha2::{Digest, Sha256};
+
+const DST: &[u8] = b"ZKRUN-BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const COMPRESSED_PUBKEY_LEN: usize = 48;
+
+/// An aggregate attestation must carry at least this many distinct signers;
+/// otherwise it's just one device's signature dressed up as a fleet report.
+const MIN_COSIGNERS: usize = 2;
+
+/// Verify `sig` as an aggregate BLS signature over `message`, produced by
+/// every public key packed (48-byte compressed, concatenated) into
+/// `pubkeys`. Returns the SHA-256 hash of each contributing public key, in
+/// the order they appear in `pubkeys`, on success.
+pub fn verify_aggregate(message: &[u8], sig: &[u8], pubkeys: &[u8]) -> Option<Vec<[u8; 32]>> {
+    if pubkeys.is_empty() || pubkeys.len() % COMPRESSED_PUBKEY_LEN != 0 {
+        return None;
+    }
+    let num_keys = pubkeys.len() / COMPRESSED_PUBKEY_LEN;
+    if num_keys < MIN_COSIGNERS {
+        return None;
+    }
+    // The same key listed twice is still one signer.
+    for (i, key) in pubkeys.chunks(COMPRESSED_PUBKEY_LEN).enumerate() {
+        if pubkeys.chunks(COMPRESSED_PUBKEY_LEN).skip(i + 1).any(|other| other == key) {
+            return None;
+        }
+    }
+
+    let sig_bytes: [u8; 96] = sig.try_into().ok()?;
+    let signature = G2Affine::from_compressed(&sig_bytes);
+    if signature.is_none().into() {
+        return None;
+    }
+    let signature = signature.unwrap();
+
+    let mut agg_pk = G1Projective::identity();
+    for chunk in pubkeys.chunks(COMPRESSED_PUBKEY_LEN) {
+        let key_bytes: [u8; 48] = chunk.try_into().ok()?;
+        let key = G1Affine::from_compressed(&key_bytes);
+        if key.is_none().into() {
+            return None;
+        }
+        agg_pk += G1Projective::from(key.unwrap());
+    }
+
+    // e(G1, sig) == e(aggregated_pk, H(msg)), checked against the common message
+    let hmsg = <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(message, DST);
+    let lhs = pairing(&G1Affine::generator(), &signature);
+    let rhs = pairing(&G1Affine::from(agg_pk), &G2Affine::from(hmsg));
+    if lhs != rhs {
+        return None;
+    }
+
+    let mut hashes = Vec::with_capacity(num_keys);
+    for chunk in pubkeys.chunks(COMPRESSED_PUBKEY_LEN) {
+        let mut hasher = Sha256::new();
+        hasher.update(chunk);
+        let out = hasher.finalize();
+        let mut h = [0u8; 32];
+        h.copy_from_slice(&out);
+        hashes.push(h);
+    }
+    Some(hashes)
+}
@@ -3,25 +3,31 @@
 
 extern crate alloc;
 
+mod bls;
+mod merkle;
+mod roughtime;
+mod webauthn;
+
 use alloc::vec::Vec;
-use k256::ecdsa::Signature as EcdsaSignature;
-use k256::ecdsa::{signature::DigestVerifier, VerifyingKey};
+use k256::ecdsa::{RecoveryId, Signature as EcdsaSignature, VerifyingKey};
 use k256::elliptic_curve::sec1::ToEncodedPoint;
 use minicbor::Decode;
 use risc0_zkvm::guest::env;
+use roughtime::RoughtimeProof;
 use sha2::{Digest, Sha256};
 use tiny_keccak::{Hasher, Keccak};
+use webauthn::WebAuthnAttestation;
 
 // Input types (decoded via CBOR)
 
 #[derive(Debug, Decode)]
-struct Sample {
+pub(crate) struct Sample {
     #[n(0)]
-    t: u64, // seconds
+    pub(crate) t: u64, // seconds
     #[n(1)]
-    lat_microdeg: i32, // degrees * 1e6
+    pub(crate) lat_microdeg: i32, // degrees * 1e6
     #[n(2)]
-    lon_microdeg: i32, // degrees * 1e6
+    pub(crate) lon_microdeg: i32, // degrees * 1e6
 }
 
 #[derive(Debug, Decode)]
@@ -37,13 +43,32 @@ struct RunInput {
     #[n(4)]
     max_speed_mps: u32, // 12
     #[n(5)]
-    blob: Vec<u8>,
+    bls_pubkeys: Vec<u8>, // scheme == SCHEME_BLS only: compressed BLS pubkeys, concatenated.
+                          // Each key must already have a verified proof of possession
+                          // from enrollment -- see `bls::verify_aggregate`.
     #[n(6)]
-    sig: Vec<u8>, // 65 bytes r||s||v
+    sig: Vec<u8>, // ECDSA: 65 bytes r||s||v. BLS: one aggregate signature.
     #[n(7)]
-    pubkey: Vec<u8>, // 65-byte uncompressed SEC1 (0x04 || X || Y)
+    roughtime: Option<RoughtimeProof>, // optional Roughtime attestation of the GPS window
+    #[n(8)]
+    scheme: u8, // SCHEME_ECDSA, SCHEME_BLS, or SCHEME_WEBAUTHN
+    #[n(9)]
+    credential_id: Vec<u8>, // scheme == SCHEME_WEBAUTHN only
+    #[n(10)]
+    credential_pubkey: Vec<u8>, // scheme == SCHEME_WEBAUTHN only: SEC1 P-256 public key
+    #[n(11)]
+    authenticator_data: Vec<u8>, // scheme == SCHEME_WEBAUTHN only
+    #[n(12)]
+    client_data_json: Vec<u8>, // scheme == SCHEME_WEBAUTHN only
 }
 
+/// Signature is a secp256k1 ECDSA signature recovered to a single Ethereum address.
+const SCHEME_ECDSA: u8 = 0;
+/// Signature is an aggregate BLS12-381 signature over several pubkeys.
+const SCHEME_BLS: u8 = 1;
+/// Signature is a WebAuthn/FIDO2 assertion from a hardware authenticator.
+const SCHEME_WEBAUTHN: u8 = 2;
+
 const EARTH_RADIUS_M: i64 = 6_371_000; // meters
 const Q: i128 = 1_i128 << 32; // Q32.32 fixed-point scale
 
@@ -122,27 +147,33 @@ fn distance_segment_meters(lat1: i32, lon1: i32, lat2: i32, lon2: i32) -> u64 {
     if meters < 0 { 0 } else { meters as u64 }
 }
 
-fn verify_signature(blob: &[u8], sig: &[u8], pubkey: &[u8]) -> Option<[u8; 20]> {
+fn verify_signature(message: &[u8], sig: &[u8]) -> Option<[u8; 20]> {
     if sig.len() != 65 { return None; }
-    if pubkey.len() != 65 { return None; }
-    // Compute SHA-256(blob)
+    // Compute SHA-256(message)
     let mut hasher = Sha256::new();
-    hasher.update(blob);
+    hasher.update(message);
     let digest = hasher.finalize();
-    // Parse signature r||s||v (ignore v)
+    // Parse signature r||s (64 bytes) and recovery byte v
     let mut sig64 = [0u8; 64];
     sig64.copy_from_slice(&sig[0..64]);
     let signature = EcdsaSignature::from_slice(&sig64).ok()?;
-    // Parse provided uncompressed public key
-    let verify_key = VerifyingKey::from_sec1_bytes(pubkey).ok()?;
-    // Verify digest
-    if verify_key.verify_digest(digest.into(), &signature).is_err() {
+    // Reject high-S signatures to match Ethereum's malleability rule
+    if signature.normalize_s().is_some() {
+        return None;
+    }
+    let v = sig[64];
+    let recovery_byte = if v >= 27 { v - 27 } else { v };
+    if recovery_byte > 1 {
         return None;
     }
+    let recid = RecoveryId::from_byte(recovery_byte)?;
+    // Recover the signing key directly from the signature and digest
+    let verify_key = VerifyingKey::recover_from_prehash(&digest, &signature, recid).ok()?;
     // Ethereum address = last 20 bytes of keccak256(uncompressed_pubkey[1..])
+    let encoded = verify_key.to_encoded_point(false);
     let mut keccak = Keccak::v256();
     let mut out = [0u8; 32];
-    keccak.update(&pubkey[1..]);
+    keccak.update(&encoded.as_bytes()[1..]);
     keccak.finalize(&mut out);
     let mut addr = [0u8; 20];
     addr.copy_from_slice(&out[12..]);
@@ -170,15 +201,57 @@ fn main() {
         return;
     }
 
-    // Signature check (and recompute blob hash)
-    let signer_addr = match verify_signature(&run_in.blob, &run_in.sig, &run_in.pubkey) {
-        Some(a) => a,
-        None => { env::commit_slice(&[0u8]); return; }
+    // Merkle-commit the GPS samples; the signature and Roughtime nonce both
+    // bind to this root instead of an arbitrary blob.
+    let root = merkle::root(&run_in.gps);
+
+    // Signature check over the committed root (scheme-dependent)
+    let signer_addr: Option<[u8; 20]>;
+    let bls_signer_hashes: Option<Vec<[u8; 32]>>;
+    let webauthn_attestation: Option<WebAuthnAttestation>;
+    match run_in.scheme {
+        SCHEME_ECDSA => match verify_signature(&root, &run_in.sig) {
+            Some(a) => { signer_addr = Some(a); bls_signer_hashes = None; webauthn_attestation = None; }
+            None => { env::commit_slice(&[0u8]); return; }
+        },
+        SCHEME_BLS => match bls::verify_aggregate(&root, &run_in.sig, &run_in.bls_pubkeys) {
+            Some(hashes) => { signer_addr = None; bls_signer_hashes = Some(hashes); webauthn_attestation = None; }
+            None => { env::commit_slice(&[0u8]); return; }
+        },
+        SCHEME_WEBAUTHN => match webauthn::verify(
+            &run_in.authenticator_data,
+            &run_in.client_data_json,
+            &run_in.sig,
+            &run_in.credential_id,
+            &run_in.credential_pubkey,
+            &root,
+        ) {
+            Some(a) => { signer_addr = None; bls_signer_hashes = None; webauthn_attestation = Some(a); }
+            None => { env::commit_slice(&[0u8]); return; }
+        },
+        _ => { env::commit_slice(&[0u8]); return; }
+    }
+
+    // Optional Roughtime attestation binding the GPS samples into a signed window
+    let time_window = match &run_in.roughtime {
+        Some(proof) => match roughtime::verify(proof, &root) {
+            Some(w) => Some(w),
+            None => { env::commit_slice(&[0u8]); return; }
+        },
+        None => None,
     };
-    // Compute blob hash (SHA-256)
-    let mut hasher = Sha256::new();
-    hasher.update(&run_in.blob);
-    let blob_hash = hasher.finalize();
+    // The nonce the server attested is the Merkle root, which only exists once the
+    // run is complete, so the attested window is necessarily close to `end_time`,
+    // not to each sample's own `t` (a run can span many minutes; `RADI` is a radius
+    // in microseconds). Only `end_time` is checked: it must not be later than the
+    // window's upper bound. `start_time` and the samples' `t` are not checked against it.
+    if let Some(ref w) = time_window {
+        let end_us = run_in.end_time.saturating_mul(1_000_000);
+        if end_us > w.hi_us {
+            env::commit_slice(&[0u8]);
+            return;
+        }
+    }
 
     // Walk samples
     let mut total_distance_m: u64 = 0;
@@ -210,13 +283,40 @@ fn main() {
         return;
     }
 
-    // Build journal: [passed=1][elapsed_sec u32 BE][blob_hash 32][signer_addr 20]
-    let mut journal: Vec<u8> = Vec::with_capacity(1 + 4 + 32 + 20);
+    // Build journal: [passed=1][elapsed_sec u32 BE][root 32][scheme 1][signer section][time_window 16]
+    // The signer section is: the 20-byte recovered address for SCHEME_ECDSA; one
+    // 32-byte SHA-256(pubkey) per co-signer for SCHEME_BLS; or
+    // SHA-256(credential_id)(32) || rp_id_hash(32) || flags(1) for SCHEME_WEBAUTHN.
+    // time_window is [lo_us u64 BE][hi_us u64 BE], zeroed when no Roughtime proof was given.
+    let mut journal: Vec<u8> = Vec::with_capacity(1 + 4 + 32 + 1 + 65 + 16);
     journal.push(1u8);
     let elapsed_u32 = if elapsed > u32::MAX as u64 { u32::MAX } else { elapsed as u32 };
     journal.extend_from_slice(&elapsed_u32.to_be_bytes());
-    journal.extend_from_slice(&blob_hash);
-    journal.extend_from_slice(&signer_addr);
+    journal.extend_from_slice(&root);
+    journal.push(run_in.scheme);
+    match run_in.scheme {
+        SCHEME_ECDSA => journal.extend_from_slice(&signer_addr.expect("checked above")),
+        SCHEME_BLS => {
+            for h in bls_signer_hashes.expect("checked above").iter() {
+                journal.extend_from_slice(h);
+            }
+        }
+        SCHEME_WEBAUTHN => {
+            let a = webauthn_attestation.expect("checked above");
+            let mut hasher = Sha256::new();
+            hasher.update(&a.credential_id);
+            journal.extend_from_slice(&hasher.finalize());
+            journal.extend_from_slice(&a.rp_id_hash);
+            journal.push(a.flags);
+        }
+        _ => unreachable!("scheme validated above"),
+    }
+    let (window_lo_us, window_hi_us) = match &time_window {
+        Some(w) => (w.lo_us, w.hi_us),
+        None => (0u64, 0u64),
+    };
+    journal.extend_from_slice(&window_lo_us.to_be_bytes());
+    journal.extend_from_slice(&window_hi_us.to_be_bytes());
     env::commit_slice(&journal);
 }
 
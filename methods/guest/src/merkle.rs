@@ -0,0 +1,61 @@
+//! Merkle commitment over GPS samples.
+//!
+//! Committing a root over the individual samples (rather than hashing one
+//! opaque blob) lets a verifier later reveal and check a single sample
+//! against the journal without re-running the proof. Leaves and internal
+//! nodes are domain-separated (`0x00` / `0x01` prefix) and the last node of
+//! an odd-length level is duplicated, Bitcoin-style.
+
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+
+use crate::Sample;
+
+fn canonical_bytes(sample: &Sample) -> [u8; 16] {
+    let mut buf = [0u8; 16];
+    buf[0..8].copy_from_slice(&sample.t.to_be_bytes());
+    buf[8..12].copy_from_slice(&sample.lat_microdeg.to_be_bytes());
+    buf[12..16].copy_from_slice(&sample.lon_microdeg.to_be_bytes());
+    buf
+}
+
+fn leaf_hash(sample: &Sample) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00u8]);
+    hasher.update(canonical_bytes(sample));
+    let out = hasher.finalize();
+    let mut h = [0u8; 32];
+    h.copy_from_slice(&out);
+    h
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01u8]);
+    hasher.update(left);
+    hasher.update(right);
+    let out = hasher.finalize();
+    let mut h = [0u8; 32];
+    h.copy_from_slice(&out);
+    h
+}
+
+/// Compute the Merkle root over `samples`. Empty input commits to the zero root.
+pub fn root(samples: &[Sample]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = samples.iter().map(leaf_hash).collect();
+    if level.is_empty() {
+        return [0u8; 32];
+    }
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity((level.len() + 1) / 2);
+        let mut i = 0;
+        while i < level.len() {
+            let left = level[i];
+            let right = if i + 1 < level.len() { level[i + 1] } else { level[i] };
+            next.push(node_hash(&left, &right));
+            i += 2;
+        }
+        level = next;
+    }
+    level[0]
+}
@@ -49,6 +49,13 @@ fn main() {
     }
     let hex = bytes.encode_hex::<String>();
     println!("IMAGE_ID=0x{}", hex);
+
+    // Also emit the Solidity verifier stub for this journal layout, generated
+    // from methods::solidity::JOURNAL_FIELDS.
+    let sol_path = "ZKRunJournal.sol";
+    let solidity_src = methods::solidity::generate_solidity_verifier();
+    std::fs::write(sol_path, solidity_src).expect("failed to write Solidity verifier stub");
+    println!("Wrote Solidity verifier stub to {}", sol_path);
 }
 
 
@@ -2,4 +2,7 @@
 // e.g., `pub mod zkrun_guest { pub const IMAGE_ID: [u32; 8]; pub const ELF: &'static [u8]; }`
 include!(concat!(env!("OUT_DIR"), "/methods.rs"));
 
+pub mod merkle;
+pub mod solidity;
+
 
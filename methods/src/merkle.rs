@@ -0,0 +1,97 @@
+//! Host-side helpers for the GPS sample Merkle commitment computed by the
+//! guest (see `methods/guest/src/merkle.rs`). Lets an off-chain verifier
+//! reveal and check a single sample against a journal's committed root
+//! without re-running the proof.
+
+use sha2::{Digest, Sha256};
+
+/// A GPS sample, canonically serialized as the guest does: `t || lat || lon`.
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub t: u64,
+    pub lat_microdeg: i32,
+    pub lon_microdeg: i32,
+}
+
+/// A Merkle inclusion path: the leaf's index and its sibling hashes from leaf to root.
+#[derive(Debug, Clone)]
+pub struct InclusionPath {
+    pub index: usize,
+    pub siblings: Vec<[u8; 32]>,
+}
+
+fn canonical_bytes(sample: &Sample) -> [u8; 16] {
+    let mut buf = [0u8; 16];
+    buf[0..8].copy_from_slice(&sample.t.to_be_bytes());
+    buf[8..12].copy_from_slice(&sample.lat_microdeg.to_be_bytes());
+    buf[12..16].copy_from_slice(&sample.lon_microdeg.to_be_bytes());
+    buf
+}
+
+fn leaf_hash(sample: &Sample) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00u8]);
+    hasher.update(canonical_bytes(sample));
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn build_levels(samples: &[Sample]) -> Vec<Vec<[u8; 32]>> {
+    let mut level: Vec<[u8; 32]> = samples.iter().map(leaf_hash).collect();
+    if level.is_empty() {
+        level.push([0u8; 32]);
+    }
+    let mut levels = vec![level.clone()];
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity((level.len() + 1) / 2);
+        let mut i = 0;
+        while i < level.len() {
+            let left = level[i];
+            let right = if i + 1 < level.len() { level[i + 1] } else { level[i] };
+            next.push(node_hash(&left, &right));
+            i += 2;
+        }
+        level = next;
+        levels.push(level.clone());
+    }
+    levels
+}
+
+/// Compute the Merkle root over `samples`, matching the guest exactly.
+pub fn root(samples: &[Sample]) -> [u8; 32] {
+    build_levels(samples).last().and_then(|l| l.first().copied()).unwrap_or([0u8; 32])
+}
+
+/// Build an inclusion path for the sample at `index`.
+pub fn inclusion_path(samples: &[Sample], index: usize) -> Option<InclusionPath> {
+    if index >= samples.len() {
+        return None;
+    }
+    let levels = build_levels(samples);
+    let mut siblings = Vec::new();
+    let mut idx = index;
+    for level in &levels[..levels.len() - 1] {
+        let sibling_idx = if idx % 2 == 0 { (idx + 1).min(level.len() - 1) } else { idx - 1 };
+        siblings.push(level[sibling_idx]);
+        idx /= 2;
+    }
+    Some(InclusionPath { index, siblings })
+}
+
+/// Verify that `sample` at `path.index` is included under `expected_root`.
+pub fn verify_inclusion(sample: &Sample, path: &InclusionPath, expected_root: &[u8; 32]) -> bool {
+    let mut acc = leaf_hash(sample);
+    let mut idx = path.index;
+    for sibling in &path.siblings {
+        acc = if idx % 2 == 0 { node_hash(&acc, sibling) } else { node_hash(sibling, &acc) };
+        idx /= 2;
+    }
+    &acc == expected_root
+}